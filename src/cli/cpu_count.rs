@@ -1,4 +1,90 @@
+/// Overrides the number of CPUs Nexus sizes its runtime for.
+pub const CPU_COUNT_ENV_VAR: &str = "NEXUS_CPU_COUNT";
+
+const CGROUP_V2_CPU_MAX: &str = "/sys/fs/cgroup/cpu.max";
+
 pub fn run() {
     println!("cpus = {}", num_cpus::get());
     println!("physical = {}", num_cpus::get_physical());
+
+    match cgroup_cpu_limit() {
+        Some(limit) => println!("cgroup limit = {}", limit),
+        None => println!("cgroup limit = none"),
+    }
+
+    println!("effective = {}", effective_cpu_count());
+}
+
+/// The number of CPUs Nexus should use: the value of `NEXUS_CPU_COUNT` when
+/// set, otherwise the detected count clamped to the container's cgroup v2
+/// quota. `num_cpus::get` already honours cgroup v1 CFS quotas but not v2's
+/// `cpu.max`, so on a cgroup v2 host it reports every core of the machine.
+pub fn effective_cpu_count() -> usize {
+    if let Ok(value) = std::env::var(CPU_COUNT_ENV_VAR) {
+        match value.parse::<usize>() {
+            Ok(count) => return count.max(1),
+            Err(e) => {
+                tracing::error!(target: "cpu_count", "Invalid {}: {}", CPU_COUNT_ENV_VAR, e)
+            }
+        }
+    }
+
+    clamp(num_cpus::get(), cgroup_cpu_limit())
+}
+
+pub fn clamp(detected: usize, limit: Option<usize>) -> usize {
+    match limit {
+        Some(limit) => detected.min(limit).max(1),
+        None => detected.max(1),
+    }
+}
+
+pub fn cgroup_cpu_limit() -> Option<usize> {
+    parse_cpu_max(&std::fs::read_to_string(CGROUP_V2_CPU_MAX).ok()?)
+}
+
+/// Parses cgroup v2's `cpu.max`, formatted as `$MAX $PERIOD` where `$MAX` is
+/// `max` when the group isn't limited.
+pub fn parse_cpu_max(content: &str) -> Option<usize> {
+    let mut words = content.split_whitespace();
+    let quota = words.next()?;
+    let period = words.next().unwrap_or("100000");
+
+    if quota == "max" {
+        return None;
+    }
+
+    cpus_from_quota(quota.parse().ok()?, period.parse().ok()?)
+}
+
+/// A quota of 1.5 periods still lets two threads make progress, so round up.
+fn cpus_from_quota(quota: u64, period: u64) -> Option<usize> {
+    if period == 0 {
+        return None;
+    }
+
+    Some(((quota + period - 1) / period).max(1) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cpu_max() {
+        assert_eq!(None, parse_cpu_max("max 100000\n"));
+        assert_eq!(Some(2), parse_cpu_max("200000 100000\n"));
+        assert_eq!(Some(2), parse_cpu_max("150000 100000\n"));
+        assert_eq!(Some(1), parse_cpu_max("50000 100000\n"));
+        assert_eq!(None, parse_cpu_max("200000 0\n"));
+        assert_eq!(None, parse_cpu_max(""));
+    }
+
+    #[test]
+    fn test_clamp() {
+        assert_eq!(2, clamp(64, Some(2)));
+        assert_eq!(8, clamp(8, Some(16)));
+        assert_eq!(8, clamp(8, None));
+        assert_eq!(1, clamp(8, Some(0)));
+    }
 }
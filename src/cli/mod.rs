@@ -1,2 +1,3 @@
 pub mod cpu_count;
 pub mod show_disk_queue_length;
+pub mod show_plugins;
@@ -0,0 +1,18 @@
+use crate::vector::app::{self, PluginsFormat};
+use structopt::StructOpt;
+
+#[derive(StructOpt, Debug)]
+#[structopt(
+    name = "show-plugins",
+    about = "Shows which vector plugins are registered"
+)]
+struct Opt {
+    /// Either `debug` or `json`
+    #[structopt(long, default_value = "debug")]
+    format: PluginsFormat,
+}
+
+pub fn run(args: Vec<String>) {
+    let args = Opt::from_iter(args.iter());
+    app::show_plugins(args.format);
+}
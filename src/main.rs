@@ -24,8 +24,8 @@ fn show_disk_queue_length(_: String, args: Vec<String>) {
     rt.block_on(crate::cli::show_disk_queue_length::run(args));
 }
 
-fn show_plugins(_: String, _: Vec<String>) {
-    crate::vector::app::show_plugins();
+fn show_plugins(_: String, args: Vec<String>) {
+    crate::cli::show_plugins::run(args);
 }
 
 fn run_internal_nexus() {
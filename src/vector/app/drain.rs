@@ -0,0 +1,127 @@
+//! Bounds how long Nexus keeps draining once it's asked to stop.
+//!
+//! Vector already reacts to SIGTERM and SIGINT by shutting the topology down
+//! gracefully, logging its progress every 5 seconds and giving up after 60.
+//! This only lets us cut that short: SIGTERM, which is what Kubernetes sends on
+//! pod termination, gets the whole grace period so in-flight events can flush.
+//! SIGINT usually means someone at a terminal, so it gets a much shorter one.
+//!
+//! Vector's own 60 seconds deadline still applies, so a grace period above it
+//! is clamped. Once the deadline passes, the process exits with status 1
+//! without waiting for Vector's teardown.
+use std::time::Duration;
+use tokio::signal::unix::{signal, SignalKind};
+
+pub const GRACE_PERIOD_ENV_VAR: &str = "NEXUS_SHUTDOWN_GRACE_SECS";
+
+/// How long `RunningTopology::stop` waits before giving up on its own.
+const VECTOR_STOP_DEADLINE: Duration = Duration::from_secs(60);
+const DEFAULT_GRACE_PERIOD: Duration = VECTOR_STOP_DEADLINE;
+const INTERRUPT_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StopSignal {
+    Terminate,
+    Interrupt,
+}
+
+pub fn drain_deadline(signal: StopSignal, grace_period: Duration) -> Duration {
+    match signal {
+        StopSignal::Terminate => grace_period,
+        StopSignal::Interrupt => grace_period.min(INTERRUPT_GRACE_PERIOD),
+    }
+}
+
+pub fn parse_grace_period(value: Option<&str>) -> Duration {
+    match value.map(str::parse::<u64>) {
+        Some(Ok(secs)) if Duration::from_secs(secs) > VECTOR_STOP_DEADLINE => {
+            warn!(
+                message = "Shutdown grace period exceeds Vector's own stop deadline, clamping it.",
+                var = GRACE_PERIOD_ENV_VAR,
+                secs,
+                max_secs = VECTOR_STOP_DEADLINE.as_secs()
+            );
+            VECTOR_STOP_DEADLINE
+        }
+        Some(Ok(secs)) => Duration::from_secs(secs),
+        Some(Err(e)) => {
+            error!(message = "Invalid shutdown grace period, using the default.", var = GRACE_PERIOD_ENV_VAR, %e);
+            DEFAULT_GRACE_PERIOD
+        }
+        None => DEFAULT_GRACE_PERIOD,
+    }
+}
+
+/// Runs on its own thread and runtime so it keeps ticking even if Vector's
+/// runtime is wedged.
+pub fn spawn_watchdog() {
+    let grace_period = parse_grace_period(std::env::var(GRACE_PERIOD_ENV_VAR).ok().as_deref());
+
+    std::thread::spawn(move || {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("couldn't create tokio runtime!");
+
+        rt.block_on(watch(grace_period));
+    });
+}
+
+async fn watch(grace_period: Duration) {
+    let mut terminate = signal(SignalKind::terminate()).expect("couldn't listen to SIGTERM");
+    let mut interrupt = signal(SignalKind::interrupt()).expect("couldn't listen to SIGINT");
+
+    let stop = tokio::select! {
+        _ = terminate.recv() => StopSignal::Terminate,
+        _ = interrupt.recv() => StopSignal::Interrupt,
+    };
+
+    let deadline = drain_deadline(stop, grace_period);
+
+    info!(message = "Draining in-flight events.", signal = ?stop, deadline_secs = deadline.as_secs());
+    tokio::time::sleep(deadline).await;
+
+    error!(
+        message = "Drain deadline exceeded, forcing shutdown.",
+        signal = ?stop,
+        deadline_secs = deadline.as_secs(),
+        exit_code = 1
+    );
+    std::process::exit(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drain_deadline() {
+        let grace_period = Duration::from_secs(30);
+
+        assert_eq!(
+            grace_period,
+            drain_deadline(StopSignal::Terminate, grace_period)
+        );
+        assert_eq!(
+            INTERRUPT_GRACE_PERIOD,
+            drain_deadline(StopSignal::Interrupt, grace_period)
+        );
+        assert_eq!(
+            Duration::from_secs(2),
+            drain_deadline(StopSignal::Interrupt, Duration::from_secs(2))
+        );
+    }
+
+    #[test]
+    fn test_parse_grace_period() {
+        assert_eq!(DEFAULT_GRACE_PERIOD, parse_grace_period(None));
+        assert_eq!(Duration::from_secs(30), parse_grace_period(Some("30")));
+        assert_eq!(DEFAULT_GRACE_PERIOD, parse_grace_period(Some("soon")));
+    }
+
+    #[test]
+    fn test_grace_period_is_clamped_to_vector_deadline() {
+        assert_eq!(VECTOR_STOP_DEADLINE, parse_grace_period(Some("60")));
+        assert_eq!(VECTOR_STOP_DEADLINE, parse_grace_period(Some("120")));
+    }
+}
@@ -1,3 +1,7 @@
+pub mod drain;
+
+use serde::Serialize;
+use std::str::FromStr;
 use tracing_subscriber::{filter::LevelFilter, FmtSubscriber};
 use vector::app::Application;
 use vector::config::SinkDescription;
@@ -44,8 +48,25 @@ pub fn change_log_var() {
     std::env::set_var("LOG", log_value);
 }
 
+// Vector sizes its runtime with num_cpus, which doesn't see cgroup v2 quotas,
+// so a CPU-limited container spawns more worker threads than we can schedule.
+// Unless the user already asked for a thread count, hand Vector our clamped
+// value.
+pub fn change_threads_var() {
+    if std::env::var("VECTOR_THREADS").is_ok() {
+        return;
+    }
+
+    let threads = crate::cli::cpu_count::effective_cpu_count();
+
+    println!("setting worker threads to {}", threads);
+    std::env::set_var("VECTOR_THREADS", threads.to_string());
+}
+
 pub fn run() {
     change_log_var();
+    change_threads_var();
+    drain::spawn_watchdog();
 
     let app = Application::prepare().unwrap_or_else(|code| {
         std::process::exit(code);
@@ -54,20 +75,74 @@ pub fn run() {
     app.run();
 }
 
-pub fn show_plugins() {
-    let sinks: Vec<String> = inventory::iter::<SinkDescription>()
-        .map(|t| t.type_str.to_string())
-        .collect();
-    let sources: Vec<String> = inventory::iter::<SourceDescription>()
-        .map(|t| t.type_str.to_string())
-        .collect();
-    let transforms: Vec<String> = inventory::iter::<TransformDescription>()
-        .map(|t| t.type_str.to_string())
-        .collect();
-
-    println!("sinks={:?}", sinks);
-    println!("sources={:?}", sources);
-    println!("transforms={:?}", transforms);
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PluginsFormat {
+    Debug,
+    Json,
+}
+
+impl FromStr for PluginsFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "debug" => Ok(PluginsFormat::Debug),
+            "json" => Ok(PluginsFormat::Json),
+            other => Err(format!(
+                "unknown format `{}`, expected `debug` or `json`",
+                other
+            )),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Plugins {
+    sinks: Vec<String>,
+    sources: Vec<String>,
+    transforms: Vec<String>,
+}
+
+impl Plugins {
+    fn registered() -> Self {
+        let sinks: Vec<String> = inventory::iter::<SinkDescription>()
+            .map(|t| t.type_str.to_string())
+            .collect();
+        let sources: Vec<String> = inventory::iter::<SourceDescription>()
+            .map(|t| t.type_str.to_string())
+            .collect();
+        let transforms: Vec<String> = inventory::iter::<TransformDescription>()
+            .map(|t| t.type_str.to_string())
+            .collect();
+
+        Plugins {
+            sinks,
+            sources,
+            transforms,
+        }
+    }
+
+    /// Sorted so the output is stable from one build to the next.
+    fn into_json(mut self) -> String {
+        self.sinks.sort();
+        self.sources.sort();
+        self.transforms.sort();
+
+        serde_json::to_string(&self).expect("plugin names are always serializable")
+    }
+}
+
+pub fn show_plugins(format: PluginsFormat) {
+    let plugins = Plugins::registered();
+
+    match format {
+        PluginsFormat::Debug => {
+            println!("sinks={:?}", plugins.sinks);
+            println!("sources={:?}", plugins.sources);
+            println!("transforms={:?}", plugins.transforms);
+        }
+        PluginsFormat::Json => println!("{}", plugins.into_json()),
+    }
 }
 
 #[cfg(test)]
@@ -90,4 +165,38 @@ mod tests {
 
         assert!(sinks.iter().any(|s| s == "grpc_stackdriver_metrics"));
     }
+
+    #[test]
+    fn plugins_json_is_sorted() {
+        let json: serde_json::Value =
+            serde_json::from_str(&super::Plugins::registered().into_json()).unwrap();
+
+        for kind in ["sinks", "sources", "transforms"] {
+            let names: Vec<&str> = json[kind]
+                .as_array()
+                .unwrap_or_else(|| panic!("{} should be an array", kind))
+                .iter()
+                .map(|name| name.as_str().unwrap())
+                .collect();
+            let mut sorted = names.clone();
+
+            sorted.sort_unstable();
+            assert_eq!(sorted, names);
+        }
+
+        assert!(json["sources"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|s| s == "disk_queue_length"));
+    }
+
+    #[test]
+    fn plugins_format_from_str() {
+        use super::PluginsFormat;
+
+        assert_eq!(Ok(PluginsFormat::Debug), "debug".parse());
+        assert_eq!(Ok(PluginsFormat::Json), "json".parse());
+        assert!("yaml".parse::<PluginsFormat>().is_err());
+    }
 }
@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+use vector::conditions::{AnyCondition, Condition, ConditionConfig, ConditionDescription};
+use vector::config::GenerateConfig;
+use vector::event::Event;
+
+/// Matches events for which every condition in `conditions` matches. Children
+/// are checked in order and the first one failing stops the check.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AllOfConfig {
+    pub conditions: Vec<AnyCondition>,
+}
+
+inventory::submit! {
+    ConditionDescription::new::<AllOfConfig>("all_of")
+}
+
+impl GenerateConfig for AllOfConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"conditions = [{ type = "is_metric" }]"#).unwrap()
+    }
+}
+
+#[typetag::serde(name = "all_of")]
+impl ConditionConfig for AllOfConfig {
+    fn build(
+        &self,
+        enrichment_tables: &enrichment::TableRegistry,
+    ) -> crate::Result<Box<dyn Condition>> {
+        if self.conditions.is_empty() {
+            return Err("all_of needs at least one condition".into());
+        }
+
+        let conditions = self
+            .conditions
+            .iter()
+            .map(|condition| condition.build(enrichment_tables))
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        Ok(Box::new(AllOf { conditions }))
+    }
+}
+
+#[derive(Clone)]
+pub struct AllOf {
+    conditions: Vec<Box<dyn Condition>>,
+}
+
+impl Condition for AllOf {
+    fn check(&self, e: &Event) -> bool {
+        self.conditions.iter().all(|condition| condition.check(e))
+    }
+
+    fn check_with_context(&self, e: &Event) -> Result<(), String> {
+        for (index, condition) in self.conditions.iter().enumerate() {
+            condition
+                .check_with_context(e)
+                .map_err(|error| format!("conditions[{}] failed: {}", index, error))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector::conditions::test_util::{build, metric};
+
+    #[test]
+    fn test_all_children_must_match() {
+        let cond = build::<AllOfConfig>(
+            r#"
+            [[conditions]]
+            type = "is_metric"
+
+            [[conditions]]
+            type = "is_log"
+            "#,
+        );
+
+        assert!(!cond.check(&metric(&[])));
+        assert!(!cond.check(&Event::from("hello")));
+        assert_eq!(
+            Err("conditions[1] failed: event is not a log type".to_string()),
+            cond.check_with_context(&metric(&[]))
+        );
+        assert_eq!(
+            Err("conditions[0] failed: event is not a metric type".to_string()),
+            cond.check_with_context(&Event::from("hello"))
+        );
+    }
+
+    #[test]
+    fn test_matches_when_every_child_matches() {
+        let cond = build::<AllOfConfig>(
+            r#"
+            [[conditions]]
+            type = "is_metric"
+
+            [[conditions]]
+            type = "is_metric"
+            "#,
+        );
+
+        assert!(cond.check(&metric(&[])));
+        assert_eq!(Ok(()), cond.check_with_context(&metric(&[])));
+    }
+
+    #[test]
+    fn test_nested_any_of() {
+        let cond = build::<AllOfConfig>(
+            r#"
+            [[conditions]]
+            type = "is_metric"
+
+            [[conditions]]
+            type = "any_of"
+
+            [[conditions.conditions]]
+            type = "is_log"
+            "#,
+        );
+
+        assert!(!cond.check(&metric(&[])));
+        assert_eq!(
+            Err("conditions[1] failed: conditions[0] failed: event is not a log type".to_string()),
+            cond.check_with_context(&metric(&[]))
+        );
+    }
+
+    #[test]
+    fn test_empty_conditions_fail_to_build() {
+        let config: AllOfConfig = toml::from_str("conditions = []").unwrap();
+
+        assert!(config.build(&Default::default()).is_err());
+    }
+}
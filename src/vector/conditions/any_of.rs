@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+use vector::conditions::{AnyCondition, Condition, ConditionConfig, ConditionDescription};
+use vector::config::GenerateConfig;
+use vector::event::Event;
+
+/// Matches events for which at least one condition in `conditions` matches.
+/// Children are checked in order and the first one matching stops the check.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AnyOfConfig {
+    pub conditions: Vec<AnyCondition>,
+}
+
+inventory::submit! {
+    ConditionDescription::new::<AnyOfConfig>("any_of")
+}
+
+impl GenerateConfig for AnyOfConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"conditions = [{ type = "is_metric" }]"#).unwrap()
+    }
+}
+
+#[typetag::serde(name = "any_of")]
+impl ConditionConfig for AnyOfConfig {
+    fn build(
+        &self,
+        enrichment_tables: &enrichment::TableRegistry,
+    ) -> crate::Result<Box<dyn Condition>> {
+        if self.conditions.is_empty() {
+            return Err("any_of needs at least one condition".into());
+        }
+
+        let conditions = self
+            .conditions
+            .iter()
+            .map(|condition| condition.build(enrichment_tables))
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        Ok(Box::new(AnyOf { conditions }))
+    }
+}
+
+#[derive(Clone)]
+pub struct AnyOf {
+    conditions: Vec<Box<dyn Condition>>,
+}
+
+impl Condition for AnyOf {
+    fn check(&self, e: &Event) -> bool {
+        self.conditions.iter().any(|condition| condition.check(e))
+    }
+
+    /// When no child matches, every child's failure is reported since any of
+    /// them could have been the one expected to match.
+    fn check_with_context(&self, e: &Event) -> Result<(), String> {
+        let mut failures = Vec::with_capacity(self.conditions.len());
+
+        for (index, condition) in self.conditions.iter().enumerate() {
+            match condition.check_with_context(e) {
+                Ok(()) => return Ok(()),
+                Err(error) => failures.push(format!("conditions[{}] failed: {}", index, error)),
+            }
+        }
+
+        Err(failures.join("; "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector::conditions::test_util::{build, metric};
+
+    #[test]
+    fn test_one_child_must_match() {
+        let cond = build::<AnyOfConfig>(
+            r#"
+            [[conditions]]
+            type = "is_log"
+
+            [[conditions]]
+            type = "is_metric"
+            "#,
+        );
+
+        assert!(cond.check(&metric(&[])));
+        assert!(cond.check(&Event::from("hello")));
+        assert_eq!(Ok(()), cond.check_with_context(&metric(&[])));
+    }
+
+    #[test]
+    fn test_every_failure_is_reported() {
+        let cond = build::<AnyOfConfig>(
+            r#"
+            [[conditions]]
+            type = "is_log"
+
+            [[conditions]]
+            type = "is_log"
+            "#,
+        );
+
+        assert!(!cond.check(&metric(&[])));
+        assert_eq!(
+            Err(concat!(
+                "conditions[0] failed: event is not a log type; ",
+                "conditions[1] failed: event is not a log type"
+            )
+            .to_string()),
+            cond.check_with_context(&metric(&[]))
+        );
+    }
+
+    #[test]
+    fn test_nested_all_of() {
+        let cond = build::<AnyOfConfig>(
+            r#"
+            [[conditions]]
+            type = "is_log"
+
+            [[conditions]]
+            type = "all_of"
+
+            [[conditions.conditions]]
+            type = "is_metric"
+            "#,
+        );
+
+        assert!(cond.check(&metric(&[])));
+        assert!(cond.check(&Event::from("hello")));
+    }
+
+    #[test]
+    fn test_empty_conditions_fail_to_build() {
+        let config: AnyOfConfig = toml::from_str("conditions = []").unwrap();
+
+        assert!(config.build(&Default::default()).is_err());
+    }
+}
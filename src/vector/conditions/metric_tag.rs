@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+use vector::conditions::{Condition, ConditionConfig, ConditionDescription};
+use vector::event::Event;
+
+/// Matches metrics carrying the `name` tag. When `value` is set, the tag must
+/// also be equal to it.
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct MetricTagConfig {
+    pub name: String,
+    pub value: Option<String>,
+}
+
+inventory::submit! {
+    ConditionDescription::new::<MetricTagConfig>("metric_tag")
+}
+
+vector::impl_generate_config_from_default!(MetricTagConfig);
+
+#[typetag::serde(name = "metric_tag")]
+impl ConditionConfig for MetricTagConfig {
+    fn build(
+        &self,
+        _enrichment_tables: &enrichment::TableRegistry,
+    ) -> crate::Result<Box<dyn Condition>> {
+        Ok(Box::new(MetricTag {
+            name: self.name.clone(),
+            value: self.value.clone(),
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MetricTag {
+    name: String,
+    value: Option<String>,
+}
+
+impl Condition for MetricTag {
+    fn check(&self, e: &Event) -> bool {
+        match e {
+            Event::Metric(metric) => match self.value.as_ref() {
+                Some(value) => metric.tag_matches(&self.name, value),
+                None => metric.tag_value(&self.name).is_some(),
+            },
+            _ => false,
+        }
+    }
+
+    fn check_with_context(&self, e: &Event) -> Result<(), String> {
+        if self.check(e) {
+            return Ok(());
+        }
+
+        match (e, self.value.as_ref()) {
+            (Event::Metric(_), Some(value)) => Err(format!(
+                "metric tag \"{}\" is missing or not equal to \"{}\"",
+                self.name, value
+            )),
+            (Event::Metric(_), None) => Err(format!("metric tag \"{}\" is missing", self.name)),
+            _ => Err("event is not a metric type".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector::conditions::test_util::{build, metric};
+    use vector::event::LogEvent;
+
+    #[test]
+    fn test_presence_only() {
+        let cond = build::<MetricTagConfig>(r#"name = "env""#);
+
+        assert!(cond.check(&metric(&[("env", "prod")])));
+        assert!(cond.check(&metric(&[("env", "staging")])));
+        assert!(!cond.check(&metric(&[("region", "eu")])));
+        assert!(!cond.check(&metric(&[])));
+        assert_eq!(
+            Err("metric tag \"env\" is missing".to_string()),
+            cond.check_with_context(&metric(&[]))
+        );
+    }
+
+    #[test]
+    fn test_value_equality() {
+        let cond = build::<MetricTagConfig>(
+            r#"
+            name = "env"
+            value = "prod"
+            "#,
+        );
+
+        assert!(cond.check(&metric(&[("env", "prod"), ("region", "eu")])));
+        assert!(!cond.check(&metric(&[("env", "staging")])));
+        assert!(!cond.check(&metric(&[("region", "prod")])));
+        assert!(cond
+            .check_with_context(&metric(&[("env", "staging")]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_logs_never_match() {
+        let cond = build::<MetricTagConfig>(r#"name = "env""#);
+        let mut log = LogEvent::default();
+
+        log.insert("env", "prod");
+        assert!(!cond.check(&Event::Log(log.clone())));
+        assert_eq!(
+            Err("event is not a metric type".to_string()),
+            cond.check_with_context(&Event::Log(log))
+        );
+    }
+}
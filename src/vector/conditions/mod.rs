@@ -0,0 +1,37 @@
+pub mod all_of;
+pub mod any_of;
+pub mod metric_tag;
+pub mod not;
+
+#[cfg(test)]
+pub(crate) mod test_util {
+    use std::collections::BTreeMap;
+    use vector::conditions::{Condition, ConditionConfig};
+    use vector::event::{Event, Metric, MetricKind, MetricValue};
+
+    pub fn metric(tags: &[(&str, &str)]) -> Event {
+        let tags = tags
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<BTreeMap<_, _>>();
+
+        Event::Metric(
+            Metric::new(
+                "requests",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )
+            .with_tags(Some(tags)),
+        )
+    }
+
+    pub fn build<C>(config: &str) -> Box<dyn Condition>
+    where
+        C: ConditionConfig + serde::de::DeserializeOwned,
+    {
+        toml::from_str::<C>(config)
+            .expect("config should parse")
+            .build(&Default::default())
+            .expect("condition should build")
+    }
+}
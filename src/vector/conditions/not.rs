@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+use vector::conditions::{AnyCondition, Condition, ConditionConfig, ConditionDescription};
+use vector::config::GenerateConfig;
+use vector::event::Event;
+
+/// Matches events for which `condition` doesn't match.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct NotConfig {
+    pub condition: AnyCondition,
+}
+
+inventory::submit! {
+    ConditionDescription::new::<NotConfig>("not")
+}
+
+impl GenerateConfig for NotConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"condition.type = "is_metric""#).unwrap()
+    }
+}
+
+#[typetag::serde(name = "not")]
+impl ConditionConfig for NotConfig {
+    fn build(
+        &self,
+        enrichment_tables: &enrichment::TableRegistry,
+    ) -> crate::Result<Box<dyn Condition>> {
+        Ok(Box::new(Not {
+            inner: self.condition.build(enrichment_tables)?,
+        }))
+    }
+}
+
+#[derive(Clone)]
+pub struct Not {
+    inner: Box<dyn Condition>,
+}
+
+impl Condition for Not {
+    fn check(&self, e: &Event) -> bool {
+        !self.inner.check(e)
+    }
+
+    fn check_with_context(&self, e: &Event) -> Result<(), String> {
+        if self.inner.check(e) {
+            Err("expected condition to fail".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector::conditions::test_util::{build, metric};
+
+    #[test]
+    fn test_flips_check() {
+        let cond = build::<NotConfig>(r#"condition.type = "is_metric""#);
+
+        assert!(!cond.check(&metric(&[])));
+        assert!(cond.check(&Event::from("hello")));
+    }
+
+    #[test]
+    fn test_flips_check_with_context() {
+        let cond = build::<NotConfig>(r#"condition.type = "is_metric""#);
+
+        assert_eq!(
+            Err("expected condition to fail".to_string()),
+            cond.check_with_context(&metric(&[]))
+        );
+        assert_eq!(Ok(()), cond.check_with_context(&Event::from("hello")));
+    }
+
+    #[test]
+    fn test_nested_in_all_of() {
+        let cond = build::<crate::vector::conditions::all_of::AllOfConfig>(
+            r#"
+            [[conditions]]
+            type = "is_metric"
+
+            [[conditions]]
+            type = "not"
+            condition.type = "is_log"
+            "#,
+        );
+
+        assert!(cond.check(&metric(&[])));
+        assert!(!cond.check(&Event::from("hello")));
+        assert_eq!(
+            Err("conditions[0] failed: event is not a metric type".to_string()),
+            cond.check_with_context(&Event::from("hello"))
+        );
+    }
+}
@@ -1,4 +1,5 @@
 pub mod app;
+pub mod conditions;
 pub mod sinks;
 pub mod sources;
 
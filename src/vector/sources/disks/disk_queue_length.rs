@@ -83,57 +83,7 @@ pub async fn get_disk_queue_length(
 #[typetag::serde(name = "disk_queue_length")]
 impl SourceConfig for DiskQueueLengthConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<vector::sources::Source> {
-        let mut out = cx
-            .out
-            .sink_map_err(|error| error!(message = "Error sending metric.", %error));
-        let mut ticks = IntervalStream::new(tokio::time::interval(Duration::from_secs(
-            self.scrape_interval_secs,
-        )))
-        .take_until(cx.shutdown);
-
-        // let disk_name = self.disk_name.clone();
-        let mut disk_regexes = Vec::new();
-
-        for regex in self.regexes.iter() {
-            disk_regexes.push(regex::Regex::new(regex.as_str())?);
-        }
-
-        let namespace = if self.namespace.is_empty() {
-            None
-        } else {
-            Some(self.namespace.clone())
-        };
-
-        Ok(Box::pin(
-            async move {
-                while ticks.next().await.is_some() {
-                    let results = get_disk_queue_length("/proc/diskstats", &disk_regexes).await;
-                    if results.is_empty() {
-                        tracing::error!(target: "disk_queue_length", "Disk not found");
-                    } else {
-                        let timestamp = chrono::Utc::now();
-                        for r in results {
-                            let mut tags = std::collections::BTreeMap::new();
-
-                            tags.insert("disk".to_string(), r.disk.to_string());
-                            let metric = Metric::new(
-                                "disk_queue_length",
-                                MetricKind::Absolute,
-                                MetricValue::Gauge { value: r.value },
-                            )
-                            .with_namespace(namespace.clone())
-                            .with_tags(Some(tags))
-                            .with_timestamp(Some(timestamp));
-                            if out.send(Event::Metric(metric)).await.is_err() {
-                                break;
-                            }
-                        }
-                    }
-                }
-            }
-            .map(Ok)
-            .boxed(),
-        ))
+        source(self, "/proc/diskstats".into(), cx)
     }
 
     fn output_type(&self) -> config::DataType {
@@ -145,6 +95,64 @@ impl SourceConfig for DiskQueueLengthConfig {
     }
 }
 
+fn source(
+    config: &DiskQueueLengthConfig,
+    diskstats: std::path::PathBuf,
+    cx: SourceContext,
+) -> crate::Result<vector::sources::Source> {
+    let mut out = cx
+        .out
+        .sink_map_err(|error| error!(message = "Error sending metric.", %error));
+    let mut ticks = IntervalStream::new(tokio::time::interval(Duration::from_secs(
+        config.scrape_interval_secs,
+    )))
+    .take_until(cx.shutdown);
+
+    // let disk_name = self.disk_name.clone();
+    let mut disk_regexes = Vec::new();
+
+    for regex in config.regexes.iter() {
+        disk_regexes.push(regex::Regex::new(regex.as_str())?);
+    }
+
+    let namespace = if config.namespace.is_empty() {
+        None
+    } else {
+        Some(config.namespace.clone())
+    };
+
+    Ok(Box::pin(
+        async move {
+            while ticks.next().await.is_some() {
+                let results = get_disk_queue_length(&diskstats, &disk_regexes).await;
+                if results.is_empty() {
+                    tracing::error!(target: "disk_queue_length", "Disk not found");
+                } else {
+                    let timestamp = chrono::Utc::now();
+                    for r in results {
+                        let mut tags = std::collections::BTreeMap::new();
+
+                        tags.insert("disk".to_string(), r.disk.to_string());
+                        let metric = Metric::new(
+                            "disk_queue_length",
+                            MetricKind::Absolute,
+                            MetricValue::Gauge { value: r.value },
+                        )
+                        .with_namespace(namespace.clone())
+                        .with_tags(Some(tags))
+                        .with_timestamp(Some(timestamp));
+                        if out.send(Event::Metric(metric)).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        .map(Ok)
+        .boxed(),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -253,4 +261,37 @@ mod tests {
         let result = get_disk_queue_length("jdfsuhvdshfvioushdfdsj", &disk_regexes).await;
         assert_eq!(Vec::<DiskQueueLengthResult>::new(), result);
     }
+
+    #[tokio::main]
+    #[test]
+    async fn test_emits_disk_queue_length_gauge() {
+        let diskstats = "   2       0 sda   1 0 4  8  9 0 13 14   2 1 2 0 0 0 0\n";
+        let mut file = tempfile::NamedTempFile::new().expect("couldn't make temp file");
+        std::io::Write::write_all(&mut file, diskstats.as_bytes()).expect("write failed");
+        let config = DiskQueueLengthConfig {
+            scrape_interval_secs: 1,
+            regexes: vec!["sda".to_string()],
+            namespace: "nexus".to_string(),
+        };
+        let (out, mut events) = vector::Pipeline::new_test();
+        let source = source(
+            &config,
+            file.path().to_path_buf(),
+            SourceContext::new_test(out),
+        )
+        .expect("source should build");
+
+        tokio::spawn(source);
+
+        let metric = tokio::time::timeout(Duration::from_secs(5), events.next())
+            .await
+            .expect("source should emit a metric")
+            .expect("source stopped")
+            .into_metric();
+
+        assert_eq!("disk_queue_length", metric.series().name.name);
+        assert_eq!(Some("nexus"), metric.series().name.namespace.as_deref());
+        assert_eq!("sda", metric.series().tags.as_ref().unwrap()["disk"]);
+        assert_eq!(MetricValue::Gauge { value: 2.0 }, metric.data().value);
+    }
 }
@@ -1,225 +1,504 @@
 use serde::de::{MapAccess, Visitor};
 use serde::{Deserialize, Deserializer};
 use std::collections::BTreeMap;
+use std::convert::TryFrom;
 use vector::event::{Metric, MetricKind, MetricValue};
 
+/// What a node reports on `/stats`. Nodes of different versions or
+/// configurations leave different parts out, and whatever is missing is
+/// skipped when building metrics. A body with none of the sections isn't
+/// stats at all though, and fails to parse.
 #[derive(Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
+#[serde(try_from = "Sections")]
 pub struct Stats {
     pub proc: Proc,
     pub sys: Sys,
     pub es: Es,
 }
 
+#[derive(Deserialize)]
+struct Sections {
+    proc: Option<Proc>,
+    sys: Option<Sys>,
+    es: Option<Es>,
+}
+
+impl TryFrom<Sections> for Stats {
+    type Error = &'static str;
+
+    fn try_from(sections: Sections) -> Result<Self, Self::Error> {
+        if sections.proc.is_none() && sections.sys.is_none() && sections.es.is_none() {
+            return Err("none of the proc, sys or es sections is present");
+        }
+
+        Ok(Stats {
+            proc: sections.proc.unwrap_or_default(),
+            sys: sections.sys.unwrap_or_default(),
+            es: sections.es.unwrap_or_default(),
+        })
+    }
+}
+
 impl Stats {
-    pub fn metrics(&self, namespace: Option<String>) -> Vec<Metric> {
-        let mut result = Vec::new();
-        let mut tags = BTreeMap::new();
-        let now = chrono::Utc::now();
-        let namespace = namespace.unwrap_or_else(|| "eventstoredb".to_string());
-
-        tags.insert("id".to_string(), self.proc.id.to_string());
-
-        result.push(
-            Metric::new(
-                "process_memory_used_bytes",
-                MetricKind::Absolute,
-                MetricValue::Gauge {
-                    value: self.proc.mem as f64,
-                },
-            )
-            .with_namespace(Some(namespace.clone()))
-            .with_tags(Some(tags.clone()))
-            .with_timestamp(Some(now)),
-        );
+    pub fn metrics(&self, namespace: &str, mut tags: BTreeMap<String, String>) -> Vec<Metric> {
+        let mut batch = Batch::new(namespace);
 
-        result.push(
-            Metric::new(
-                "disk_io_read_bytes",
-                MetricKind::Absolute,
-                MetricValue::Counter {
-                    value: self.proc.disk_io.read_bytes as f64,
-                },
-            )
-            .with_namespace(Some(namespace.clone()))
-            .with_tags(Some(tags.clone()))
-            .with_timestamp(Some(now)),
-        );
+        if let Some(id) = self.proc.id {
+            tags.insert("id".to_string(), id.to_string());
+        }
 
-        result.push(
-            Metric::new(
-                "disk_io_written_bytes",
-                MetricKind::Absolute,
-                MetricValue::Counter {
-                    value: self.proc.disk_io.written_bytes as f64,
-                },
-            )
-            .with_namespace(Some(namespace.clone()))
-            .with_tags(Some(tags.clone()))
-            .with_timestamp(Some(now)),
+        batch.gauge(
+            "process_memory_used_bytes",
+            "proc/mem",
+            self.proc.mem,
+            &tags,
         );
-
-        result.push(
-            Metric::new(
-                "disk_io_read_ops",
-                MetricKind::Absolute,
-                MetricValue::Counter {
-                    value: self.proc.disk_io.read_ops as f64,
-                },
-            )
-            .with_namespace(Some(namespace.clone()))
-            .with_tags(Some(tags.clone()))
-            .with_timestamp(Some(now)),
+        batch.gauge("process_cpu", "proc/cpu", self.proc.cpu, &tags);
+        batch.gauge(
+            "process_threads",
+            "proc/threadsCount",
+            self.proc.threads_count,
+            &tags,
+        );
+        batch.gauge(
+            "process_thrown_exceptions_rate",
+            "proc/thrownExceptionsRate",
+            self.proc.thrown_exceptions_rate,
+            &tags,
         );
 
-        result.push(
-            Metric::new(
+        if let Some(disk_io) = self.proc.disk_io.as_ref() {
+            batch.counter(
+                "disk_io_read_bytes",
+                "proc/diskIo/readBytes",
+                disk_io.read_bytes,
+                &tags,
+            );
+            batch.counter(
+                "disk_io_written_bytes",
+                "proc/diskIo/writtenBytes",
+                disk_io.written_bytes,
+                &tags,
+            );
+            batch.counter(
+                "disk_io_read_ops",
+                "proc/diskIo/readOps",
+                disk_io.read_ops,
+                &tags,
+            );
+            batch.counter(
                 "disk_io_write_ops",
-                MetricKind::Absolute,
-                MetricValue::Counter {
-                    value: self.proc.disk_io.write_ops as f64,
-                },
-            )
-            .with_namespace(Some(namespace.clone()))
-            .with_tags(Some(tags.clone()))
-            .with_timestamp(Some(now)),
-        );
+                "proc/diskIo/writeOps",
+                disk_io.write_ops,
+                &tags,
+            );
+        }
 
-        result.push(
-            Metric::new(
-                "free_memory",
-                MetricKind::Absolute,
-                MetricValue::Gauge {
-                    value: self.sys.free_mem as f64,
-                },
-            )
-            .with_namespace(Some(namespace.clone()))
-            .with_tags(Some(tags.clone()))
-            .with_timestamp(Some(now)),
-        );
+        if let Some(tcp) = self.proc.tcp.as_ref() {
+            batch.gauge(
+                "tcp_connections",
+                "proc/tcp/connections",
+                tcp.connections,
+                &tags,
+            );
+            batch.counter(
+                "tcp_received_bytes",
+                "proc/tcp/receivedBytesTotal",
+                tcp.received_bytes_total,
+                &tags,
+            );
+            batch.counter(
+                "tcp_sent_bytes",
+                "proc/tcp/sentBytesTotal",
+                tcp.sent_bytes_total,
+                &tags,
+            );
+            batch.gauge(
+                "tcp_pending_received",
+                "proc/tcp/pendingReceived",
+                tcp.pending_received,
+                &tags,
+            );
+            batch.gauge(
+                "tcp_pending_send",
+                "proc/tcp/pendingSend",
+                tcp.pending_send,
+                &tags,
+            );
+        }
+
+        if let Some(gc) = self.proc.gc.as_ref() {
+            let generations = [
+                ("0", gc.gen0_items_count, gc.gen0_size),
+                ("1", gc.gen1_items_count, gc.gen1_size),
+                ("2", gc.gen2_items_count, gc.gen2_size),
+            ];
+
+            for (generation, collections, size) in generations {
+                let mut gen_tags = tags.clone();
+
+                gen_tags.insert("generation".to_string(), generation.to_string());
+                batch.counter(
+                    "gc_collections",
+                    &format!("proc/gc/gen{}ItemsCount", generation),
+                    collections,
+                    &gen_tags,
+                );
+                batch.gauge(
+                    "gc_generation_size_bytes",
+                    &format!("proc/gc/gen{}Size", generation),
+                    size,
+                    &gen_tags,
+                );
+            }
+
+            batch.gauge(
+                "gc_large_heap_size_bytes",
+                "proc/gc/largeHeapSize",
+                gc.large_heap_size,
+                &tags,
+            );
+            batch.gauge(
+                "gc_heaps_size_bytes",
+                "proc/gc/totalBytesInHeaps",
+                gc.total_bytes_in_heaps,
+                &tags,
+            );
+            batch.gauge("gc_time_percent", "proc/gc/timeInGc", gc.time_in_gc, &tags);
+        }
+
+        batch.gauge("free_memory", "sys/freeMem", self.sys.free_mem, &tags);
+        batch.gauge("sys_cpu", "sys/cpu", self.sys.cpu, &tags);
+
+        if let Some(loadavg) = self.sys.loadavg.as_ref() {
+            for (interval, value) in [
+                ("1m", loadavg.one_m),
+                ("5m", loadavg.five_m),
+                ("15m", loadavg.fifteen_m),
+            ] {
+                let mut load_tags = tags.clone();
+
+                load_tags.insert("interval".to_string(), interval.to_string());
+                batch.gauge(
+                    "load_average",
+                    &format!("sys/loadavg/{}", interval),
+                    value,
+                    &load_tags,
+                );
+            }
+        }
 
         for queue in self.es.queues.iter() {
             let mut queue_tags = tags.clone();
+            let stat = |key: &str| format!("es/queue/{}/{}", queue.name, key);
 
             queue_tags.insert("name".to_string(), queue.name.clone());
-            result.push(
-                Metric::new(
-                    "queue_length",
-                    MetricKind::Absolute,
-                    MetricValue::Gauge {
-                        value: queue.length as f64,
-                    },
-                )
-                .with_namespace(Some(namespace.clone()))
-                .with_tags(Some(queue_tags.clone()))
-                .with_timestamp(Some(now)),
+            batch.gauge("queue_length", &stat("length"), queue.length, &queue_tags);
+            batch.gauge(
+                "queue_avg_processing_time",
+                &stat("avgProcessingTime"),
+                queue.avg_processing_time,
+                &queue_tags,
             );
-
-            result.push(
-                Metric::new(
-                    "queue_avg_processing_time",
-                    MetricKind::Absolute,
-                    MetricValue::Gauge {
-                        value: queue.avg_processing_time,
-                    },
-                )
-                .with_namespace(Some(namespace.clone()))
-                .with_tags(Some(queue_tags.clone()))
-                .with_timestamp(Some(now)),
+            batch.gauge(
+                "queue_length_lifetime_peak",
+                &stat("lengthLifetimePeak"),
+                queue.length_lifetime_peak,
+                &queue_tags,
+            );
+            batch.gauge(
+                "queue_avg_items_per_second",
+                &stat("avgItemsPerSecond"),
+                queue.avg_items_per_second,
+                &queue_tags,
+            );
+            batch.gauge(
+                "queue_idle_time_percent",
+                &stat("idleTimePercent"),
+                queue.idle_time_percent,
+                &queue_tags,
+            );
+            batch.counter(
+                "queue_items_processed",
+                &stat("totalItemsProcessed"),
+                queue.total_items_processed,
+                &queue_tags,
             );
         }
 
-        if let Some(drive) = self.sys.drive.as_ref() {
-            tags.insert("path".to_string(), drive.path.clone());
-
-            result.push(
-                Metric::new(
-                    "drive_total_bytes",
-                    MetricKind::Absolute,
-                    MetricValue::Gauge {
-                        value: drive.stats.total_bytes as f64,
-                    },
-                )
-                .with_namespace(Some(namespace.clone()))
-                .with_tags(Some(tags.clone()))
-                .with_timestamp(Some(now)),
+        if let Some(writer) = self.es.writer.as_ref() {
+            batch.gauge(
+                "writer_last_flush_size",
+                "es/writer/lastFlushSize",
+                writer.last_flush_size,
+                &tags,
+            );
+            batch.gauge(
+                "writer_mean_flush_size",
+                "es/writer/meanFlushSize",
+                writer.mean_flush_size,
+                &tags,
             );
+            batch.gauge(
+                "writer_max_flush_size",
+                "es/writer/maxFlushSize",
+                writer.max_flush_size,
+                &tags,
+            );
+            batch.gauge(
+                "writer_last_flush_delay_ms",
+                "es/writer/lastFlushDelayMs",
+                writer.last_flush_delay_ms,
+                &tags,
+            );
+            batch.gauge(
+                "writer_mean_flush_delay_ms",
+                "es/writer/meanFlushDelayMs",
+                writer.mean_flush_delay_ms,
+                &tags,
+            );
+            batch.gauge(
+                "writer_max_flush_delay_ms",
+                "es/writer/maxFlushDelayMs",
+                writer.max_flush_delay_ms,
+                &tags,
+            );
+            batch.gauge(
+                "writer_queued_flush_messages",
+                "es/writer/queuedFlushMessages",
+                writer.queued_flush_messages,
+                &tags,
+            );
+        }
+
+        if let Some(read_index) = self.es.read_index.as_ref() {
+            let caches = [
+                (
+                    "record",
+                    "Record",
+                    read_index.cached_record,
+                    read_index.not_cached_record,
+                ),
+                (
+                    "stream_info",
+                    "StreamInfo",
+                    read_index.cached_stream_info,
+                    read_index.not_cached_stream_info,
+                ),
+                (
+                    "trans_info",
+                    "TransInfo",
+                    read_index.cached_trans_info,
+                    read_index.not_cached_trans_info,
+                ),
+            ];
+
+            for (cache, key, hits, misses) in caches {
+                let mut cache_tags = tags.clone();
+
+                cache_tags.insert("cache".to_string(), cache.to_string());
+                batch.counter(
+                    "read_index_cache_hits",
+                    &format!("es/readIndex/cached{}", key),
+                    hits,
+                    &cache_tags,
+                );
+                batch.counter(
+                    "read_index_cache_misses",
+                    &format!("es/readIndex/notCached{}", key),
+                    misses,
+                    &cache_tags,
+                );
+            }
+        }
+
+        for drive in self.sys.drives.iter() {
+            let mut drive_tags = tags.clone();
+            let stat = |key: &str| format!("sys/drive/{}/{}", drive.path, key);
 
-            result.push(
-                Metric::new(
-                    "drive_available_bytes",
-                    MetricKind::Absolute,
-                    MetricValue::Gauge {
-                        value: drive.stats.available_bytes as f64,
-                    },
-                )
-                .with_namespace(Some(namespace.clone()))
-                .with_tags(Some(tags.clone()))
-                .with_timestamp(Some(now)),
+            drive_tags.insert("path".to_string(), drive.path.clone());
+            batch.gauge(
+                "drive_total_bytes",
+                &stat("totalBytes"),
+                drive.stats.total_bytes,
+                &drive_tags,
             );
+            batch.gauge(
+                "drive_available_bytes",
+                &stat("availableBytes"),
+                drive.stats.available_bytes,
+                &drive_tags,
+            );
+            batch.gauge(
+                "drive_used_bytes",
+                &stat("usedBytes"),
+                drive.stats.used_bytes,
+                &drive_tags,
+            );
+        }
 
-            result.push(
-                Metric::new(
-                    "drive_used_bytes",
-                    MetricKind::Absolute,
-                    MetricValue::Gauge {
-                        value: drive.stats.used_bytes as f64,
-                    },
-                )
-                .with_namespace(Some(namespace))
+        batch.metrics
+    }
+}
+
+/// Metrics sharing a namespace and a timestamp. Each metric is tagged with
+/// the `/stats` path it was read from as `esdb_stat`. Stats that the node
+/// didn't report are skipped.
+struct Batch<'a> {
+    namespace: &'a str,
+    now: chrono::DateTime<chrono::Utc>,
+    metrics: Vec<Metric>,
+}
+
+impl<'a> Batch<'a> {
+    fn new(namespace: &'a str) -> Self {
+        Self {
+            namespace,
+            now: chrono::Utc::now(),
+            metrics: Vec::new(),
+        }
+    }
+
+    fn push(
+        &mut self,
+        name: &str,
+        stat: &str,
+        value: MetricValue,
+        tags: &BTreeMap<String, String>,
+    ) {
+        let mut tags = tags.clone();
+
+        tags.insert("esdb_stat".to_string(), stat.to_string());
+        self.metrics.push(
+            Metric::new(name, MetricKind::Absolute, value)
+                .with_namespace(Some(self.namespace.to_string()))
                 .with_tags(Some(tags))
-                .with_timestamp(Some(now)),
-            );
+                .with_timestamp(Some(self.now)),
+        );
+    }
+
+    fn gauge(
+        &mut self,
+        name: &str,
+        stat: &str,
+        value: Option<f64>,
+        tags: &BTreeMap<String, String>,
+    ) {
+        if let Some(value) = value {
+            self.push(name, stat, MetricValue::Gauge { value }, tags);
         }
+    }
 
-        result
+    fn counter(
+        &mut self,
+        name: &str,
+        stat: &str,
+        value: Option<f64>,
+        tags: &BTreeMap<String, String>,
+    ) {
+        if let Some(value) = value {
+            self.push(name, stat, MetricValue::Counter { value }, tags);
+        }
     }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Proc {
-    pub id: usize,
-    pub mem: usize,
-    pub cpu: f64,
-    pub threads_count: i64,
-    pub thrown_exceptions_rate: f64,
-    pub disk_io: DiskIo,
+    pub id: Option<u64>,
+    pub mem: Option<f64>,
+    pub cpu: Option<f64>,
+    pub threads_count: Option<f64>,
+    pub thrown_exceptions_rate: Option<f64>,
+    pub disk_io: Option<DiskIo>,
+    pub tcp: Option<Tcp>,
+    pub gc: Option<Gc>,
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct DiskIo {
-    pub read_bytes: usize,
-    pub written_bytes: usize,
-    pub read_ops: usize,
-    pub write_ops: usize,
+    pub read_bytes: Option<f64>,
+    pub written_bytes: Option<f64>,
+    pub read_ops: Option<f64>,
+    pub write_ops: Option<f64>,
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
+pub struct Tcp {
+    pub connections: Option<f64>,
+    pub received_bytes_total: Option<f64>,
+    pub sent_bytes_total: Option<f64>,
+    pub pending_received: Option<f64>,
+    pub pending_send: Option<f64>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Gc {
+    pub gen0_items_count: Option<f64>,
+    pub gen0_size: Option<f64>,
+    pub gen1_items_count: Option<f64>,
+    pub gen1_size: Option<f64>,
+    pub gen2_items_count: Option<f64>,
+    pub gen2_size: Option<f64>,
+    pub large_heap_size: Option<f64>,
+    pub time_in_gc: Option<f64>,
+    pub total_bytes_in_heaps: Option<f64>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
 pub struct Sys {
-    pub free_mem: usize,
-    pub loadavg: LoadAvg,
-    pub drive: Option<Drive>,
+    pub free_mem: Option<f64>,
+    pub cpu: Option<f64>,
+    pub loadavg: Option<LoadAvg>,
+    #[serde(rename = "drive", default, deserialize_with = "deserialize_drives")]
+    pub drives: Vec<Drive>,
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct LoadAvg {
     #[serde(rename = "1m")]
-    pub one_m: f64,
+    pub one_m: Option<f64>,
     #[serde(rename = "5m")]
-    pub five_m: f64,
+    pub five_m: Option<f64>,
     #[serde(rename = "15m")]
-    pub fifteen_m: f64,
+    pub fifteen_m: Option<f64>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Es {
-    #[serde(rename = "queue", deserialize_with = "deserialize_queues")]
+    #[serde(rename = "queue", default, deserialize_with = "deserialize_queues")]
     pub queues: Vec<Queue>,
+    pub writer: Option<Writer>,
+    pub read_index: Option<ReadIndex>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Writer {
+    pub last_flush_size: Option<f64>,
+    pub last_flush_delay_ms: Option<f64>,
+    pub mean_flush_size: Option<f64>,
+    pub mean_flush_delay_ms: Option<f64>,
+    pub max_flush_size: Option<f64>,
+    pub max_flush_delay_ms: Option<f64>,
+    pub queued_flush_messages: Option<f64>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadIndex {
+    pub cached_record: Option<f64>,
+    pub not_cached_record: Option<f64>,
+    pub cached_stream_info: Option<f64>,
+    pub not_cached_stream_info: Option<f64>,
+    pub cached_trans_info: Option<f64>,
+    pub not_cached_trans_info: Option<f64>,
 }
 
 fn deserialize_queues<'de, D>(
@@ -259,9 +538,17 @@ pub struct Queue {
     #[serde(rename = "queueName")]
     pub name: String,
 
-    pub length: usize,
+    pub length: Option<f64>,
+
+    pub avg_processing_time: Option<f64>,
+
+    pub length_lifetime_peak: Option<f64>,
+
+    pub avg_items_per_second: Option<f64>,
 
-    pub avg_processing_time: f64,
+    pub idle_time_percent: Option<f64>,
+
+    pub total_items_processed: Option<f64>,
 }
 
 #[derive(Debug)]
@@ -270,44 +557,249 @@ pub struct Drive {
     pub stats: DriveStats,
 }
 
-impl<'de> Deserialize<'de> for Drive {
-    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        deserializer.deserialize_map(DriveVisitor)
-    }
+fn deserialize_drives<'de, D>(
+    deserializer: D,
+) -> Result<Vec<Drive>, <D as Deserializer<'de>>::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_map(DrivesVisitor)
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct DriveStats {
-    pub available_bytes: usize,
-    pub total_bytes: usize,
-    pub usage: String,
-    pub used_bytes: usize,
+    pub available_bytes: Option<f64>,
+    pub total_bytes: Option<f64>,
+    pub used_bytes: Option<f64>,
 }
 
-struct DriveVisitor;
+struct DrivesVisitor;
 
-impl<'de> Visitor<'de> for DriveVisitor {
-    type Value = Drive;
+impl<'de> Visitor<'de> for DrivesVisitor {
+    type Value = Vec<Drive>;
 
     fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(formatter, "DriveStats object")
+        write!(formatter, "Drives object")
     }
 
     fn visit_map<A>(self, mut map: A) -> Result<Self::Value, <A as MapAccess<'de>>::Error>
     where
         A: MapAccess<'de>,
     {
-        if let Some(key) = map.next_key()? {
-            return Ok(Drive {
-                path: key,
+        let mut drives: Vec<Drive> = Vec::new();
+        while let Some(path) = map.next_key()? {
+            drives.push(Drive {
+                path,
                 stats: map.next_value()?,
             });
         }
 
-        Err(serde::de::Error::missing_field("<Drive path>"))
+        Ok(drives)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Hand-written, it covers every section and field the source reads.
+    const FIXTURE: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/eventstoredb/stats.json"
+    ));
+
+    fn find<'a>(metrics: &'a [Metric], name: &str, tag: Option<(&str, &str)>) -> &'a Metric {
+        metrics
+            .iter()
+            .filter(|m| m.series().name.name == name)
+            .find(|m| match tag {
+                Some((key, value)) => {
+                    m.series()
+                        .tags
+                        .as_ref()
+                        .unwrap()
+                        .get(key)
+                        .map(String::as_str)
+                        == Some(value)
+                }
+                None => true,
+            })
+            .unwrap_or_else(|| panic!("missing {} metric", name))
+    }
+
+    #[test]
+    fn test_parse_full_stats() {
+        let stats: Stats = serde_json::from_str(FIXTURE).expect("fixture should parse");
+        let metrics = stats.metrics("eventstoredb", BTreeMap::new());
+
+        assert_eq!(
+            MetricValue::Counter { value: 1024.0 },
+            find(&metrics, "tcp_received_bytes", None).data().value
+        );
+        assert_eq!(
+            MetricValue::Counter { value: 1234.0 },
+            find(
+                &metrics,
+                "queue_items_processed",
+                Some(("name", "MainQueue"))
+            )
+            .data()
+            .value
+        );
+        assert_eq!(
+            MetricValue::Gauge { value: 0.5 },
+            find(&metrics, "load_average", Some(("interval", "5m")))
+                .data()
+                .value
+        );
+        assert_eq!(
+            MetricValue::Counter { value: 12.0 },
+            find(&metrics, "gc_collections", Some(("generation", "1")))
+                .data()
+                .value
+        );
+        assert_eq!(
+            MetricValue::Counter { value: 15.0 },
+            find(
+                &metrics,
+                "read_index_cache_misses",
+                Some(("cache", "stream_info"))
+            )
+            .data()
+            .value
+        );
+        assert_eq!(
+            MetricValue::Gauge { value: 3.0 },
+            find(&metrics, "writer_queued_flush_messages", None)
+                .data()
+                .value
+        );
+        assert_eq!(
+            MetricValue::Gauge {
+                value: 50_000_000_000.0
+            },
+            find(
+                &metrics,
+                "drive_available_bytes",
+                Some(("path", "/var/lib/eventstore"))
+            )
+            .data()
+            .value
+        );
+    }
+
+    #[test]
+    fn test_missing_sections_are_skipped() {
+        let stats: Stats = serde_json::from_str(
+            r#"{
+                "proc": {
+                    "id": 1, "mem": 10, "cpu": 0.0, "threadsCount": 1, "thrownExceptionsRate": 0.0,
+                    "diskIo": { "readBytes": 0, "writtenBytes": 0, "readOps": 0, "writeOps": 0 }
+                },
+                "sys": {
+                    "freeMem": 0,
+                    "drive": {
+                        "/data": { "availableBytes": 1, "totalBytes": 3, "usage": "66%", "usedBytes": 2 },
+                        "/index": { "availableBytes": 4, "totalBytes": 6, "usage": "33%", "usedBytes": 2 }
+                    }
+                },
+                "es": {}
+            }"#,
+        )
+        .expect("partial stats should parse");
+        let metrics = stats.metrics("eventstoredb", BTreeMap::new());
+
+        assert!(!metrics
+            .iter()
+            .any(|m| m.series().name.name.starts_with("tcp_")));
+        assert!(!metrics
+            .iter()
+            .any(|m| m.series().name.name.starts_with("writer_")));
+        assert!(!metrics
+            .iter()
+            .any(|m| m.series().name.name == "load_average"));
+        assert_eq!(
+            2,
+            metrics
+                .iter()
+                .filter(|m| m.series().name.name == "drive_total_bytes")
+                .count()
+        );
+    }
+
+    #[test]
+    fn test_body_without_sections_fails_to_parse() {
+        assert!(serde_json::from_str::<Stats>("{}").is_err());
+        assert!(serde_json::from_str::<Stats>(r#"{ "error": "Unauthorized" }"#).is_err());
+    }
+
+    #[test]
+    fn test_missing_fields_are_skipped() {
+        let stats: Stats = serde_json::from_str(
+            r#"{
+                "proc": {
+                    "id": 1, "mem": 10, "threadsCount": 1,
+                    "diskIo": { "readBytes": 5, "writeOps": 0 }
+                },
+                "sys": {
+                    "loadavg": { "1m": 0.5, "5m": 0.25 },
+                    "drive": {
+                        "/data": { "availableBytes": 1, "totalBytes": 3 }
+                    }
+                }
+            }"#,
+        )
+        .expect("stats with missing fields should parse");
+        let metrics = stats.metrics("eventstoredb", BTreeMap::new());
+        let names = metrics
+            .iter()
+            .map(|m| m.series().name.name.as_str())
+            .collect::<Vec<_>>();
+
+        for missing in [
+            "process_cpu",
+            "process_thrown_exceptions_rate",
+            "disk_io_written_bytes",
+            "free_memory",
+            "drive_used_bytes",
+        ] {
+            assert!(!names.contains(&missing), "{} should be skipped", missing);
+        }
+
+        assert_eq!(
+            MetricValue::Counter { value: 5.0 },
+            find(&metrics, "disk_io_read_bytes", None).data().value
+        );
+        assert_eq!(
+            MetricValue::Gauge { value: 3.0 },
+            find(&metrics, "drive_total_bytes", None).data().value
+        );
+        assert_eq!(
+            2,
+            metrics
+                .iter()
+                .filter(|m| m.series().name.name == "load_average")
+                .count()
+        );
+        assert!(!names.iter().any(|name| name.starts_with("queue_")));
+    }
+
+    #[test]
+    fn test_metrics_carry_their_stat_path() {
+        let stats: Stats = serde_json::from_str(FIXTURE).expect("fixture should parse");
+        let metrics = stats.metrics("eventstoredb", BTreeMap::new());
+        let metric = find(&metrics, "disk_io_read_bytes", None);
+
+        assert_eq!(
+            "proc/diskIo/readBytes",
+            metric.series().tags.as_ref().unwrap()["esdb_stat"]
+        );
+
+        let metric = find(&metrics, "queue_length", Some(("name", "Worker #1")));
+        assert_eq!(
+            "es/queue/Worker #1/length",
+            metric.series().tags.as_ref().unwrap()["esdb_stat"]
+        );
     }
 }